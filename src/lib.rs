@@ -20,9 +20,11 @@
 //! 
 //! // The following moves the slice 3..6 to index 1.
 //! // In effect, it moves [4,5,6] over to where [2] is.
-//! arr.moveslice(3..6, 1);
+//! // It also returns the range the chunk ended up at.
+//! let moved = arr.moveslice(3..6, 1);
 //! assert_eq!(arr, [1,4,5,6,2,3,7,8,9]);
-//! 
+//! assert_eq!(moved, 1..4);
+//!
 //! // The following moves the slice 3..6 to index 6.
 //! // In effect, it moves [6,2,3] over to where [7] is.
 //! arr.moveslice(3..6, 6);
@@ -50,11 +52,40 @@
 //! // InvalidBounds error.
 //! let res = arr.try_moveslice(9..10, 7);
 //! assert!(if let Err(Error::InvalidBounds{..}) = res {true} else {false});
-//! 
+//!
+//! // An inverted range also triggers an error of its own, once
+//! // normalized. `(Excluded(5), Excluded(5))` normalizes to
+//! // `start: 6, end: 5`, which is a StartGreaterThanEnd error.
+//! use core::ops::Bound::Excluded;
+//! let res = arr.try_moveslice((Excluded(5), Excluded(5)), 0);
+//! assert!(if let Err(Error::StartGreaterThanEnd{..}) = res {true} else {false});
+//!
 //! // You could pass the destination as the same value as chunk.0.
 //! // However this would mean nothing is moved.
 //! // This doesn't panic, but it's a no-op.
 //! arr.moveslice(0..3, 0);
+//!
+//! // `swapslice` exchanges two disjoint subslices, leaving the gap
+//! // between them untouched. The following swaps [1] (at 0..1) with
+//! // [7,8] (at 6..8) in an array of len 9; the ranges don't need to be
+//! // the same length.
+//! let mut arr = [1,2,3,4,5,6,7,8,9];
+//! arr.swapslice(0..1, 6..8);
+//! assert_eq!(arr, [7,8,2,3,4,5,6,1,9]);
+//!
+//! // Swapping two overlapping ranges triggers an OverlappingRanges error.
+//! let res = arr.try_swapslice(0..3, 2..5);
+//! assert!(if let Err(Error::OverlappingRanges{..}) = res {true} else {false});
+//!
+//! // `moveslice_clamped` behaves like `moveslice`, but instead of
+//! // erroring on an overshooting destination, it clamps the chunk as
+//! // far in the requested direction as it can fit. Moving [4,5,6]
+//! // (3..6) to destination 7 would put it at 7..10, which doesn't fit
+//! // in an array of len 9, so it lands at 6..9 instead.
+//! let mut arr = [1,2,3,4,5,6,7,8,9];
+//! let landed = arr.moveslice_clamped(3..6, 7);
+//! assert_eq!(arr, [1,2,3,7,8,9,4,5,6]);
+//! assert_eq!(landed, 6..9);
 //! ```
 //! 
 //! [split-at-mut]: https://doc.rust-lang.org/std/primitive.slice.html#method.split_at_mut
@@ -62,18 +93,19 @@
 //! [rotate-right]: https://doc.rust-lang.org/std/primitive.slice.html#method.rotate_right
 
 use core::ops::Bound::*;
+use core::ops::Range;
 use core::ops::RangeBounds;
 
-/// This Error enum has a single variant, which is used to return additional information about
-/// the out of bounds error, to help diagnostics.
-/// 
+/// This Error enum is used to return additional information about what went
+/// wrong, to help diagnostics.
+///
 /// Is used/returned by `try_moveslice`.
 #[derive(Debug)]
 pub enum Error {
     /// This error signifies an out of bounds error.
-    /// It also contains the length of the slice, and 
+    /// It also contains the length of the slice, and
     /// the supposed location of where the chunk would have been.
-    /// 
+    ///
     /// For example:
     /// `OutOfBoundsMove {len: 10, dest: (8,11)}`
     OutOfBoundsMove {
@@ -84,7 +116,7 @@ pub enum Error {
     },
 
     /// This error signifies an invalid bounds error.
-    /// If the bounds passed are already out of bounds, this 
+    /// If the bounds passed are already out of bounds, this
     /// error is returned instead. This is to differentiate
     /// between the two out-of-bounds cases.
     InvalidBounds {
@@ -92,7 +124,99 @@ pub enum Error {
         len: usize,
         // The effective bounds passed in.
         bounds: (usize, usize)
+    },
+
+    /// This error signifies that the normalized bounds are inverted, i.e.
+    /// the start of the range lies after its end.
+    ///
+    /// For example, an `Excluded` start bound of `5` paired with an
+    /// `Excluded` end bound of `5` normalizes to `start: 6, end: 5`, which
+    /// triggers this error.
+    StartGreaterThanEnd {
+        /// The normalized start of the range.
+        start: usize,
+        /// The normalized end of the range.
+        end: usize
+    },
+
+    /// This error signifies that the two ranges passed to `try_swapslice`
+    /// overlap, so they cannot be swapped.
+    OverlappingRanges {
+        /// The normalized bounds of the first range.
+        a: (usize, usize),
+        /// The normalized bounds of the second range.
+        b: (usize, usize)
+    }
+}
+
+/// Lowers any `RangeBounds<usize>` to a concrete `(start, end)` pair against a
+/// slice of the given length, modeled on std's internal `slice::range`/
+/// `check_range` helpers.
+///
+/// `Included`/`Excluded`/`Unbounded` start and end bounds are all handled
+/// explicitly, including the `Excluded` start bounds and inclusive
+/// `usize::MAX` end bounds that a hand-rolled `Included`-only parse would
+/// get wrong. Arithmetic that would overflow `usize` is reported as
+/// `Error::InvalidBounds` instead of panicking, and an inverted range
+/// (`start > end`) is reported as `Error::StartGreaterThanEnd`.
+fn normalize_bounds<R: RangeBounds<usize>>(bounds: &R, len: usize) -> Result<(usize, usize), Error> {
+    let start = match bounds.start_bound() {
+        Included(&s) => s,
+        Excluded(&s) => match s.checked_add(1) {
+            Some(s) => s,
+            None => return Err(Error::InvalidBounds { len, bounds: (s, len) }),
+        },
+        Unbounded => 0,
+    };
+
+    let end = match bounds.end_bound() {
+        Included(&e) => match e.checked_add(1) {
+            Some(e) => e,
+            None => return Err(Error::InvalidBounds { len, bounds: (start, e) }),
+        },
+        Excluded(&e) => e,
+        Unbounded => len,
+    };
+
+    if start > end {
+        return Err(Error::StartGreaterThanEnd { start, end });
+    }
+
+    if end > len {
+        return Err(Error::InvalidBounds { len, bounds: (start, end) });
+    }
+
+    Ok((start, end))
+}
+
+/// Moves the already-validated `chunk` within `slice` so that it starts at
+/// `destination`, and reports the chunk's new location.
+///
+/// Assumes `chunk` is a valid range within `slice` and that `destination +
+/// (chunk.1 - chunk.0)` does not exceed `slice.len()`; callers are
+/// responsible for checking both before calling this.
+fn move_chunk<T>(slice: &mut [T], chunk: (usize, usize), destination: usize) -> Range<usize> {
+    let chunksize = chunk.1 - chunk.0;
+
+    if destination > chunk.0 {
+        let index1 = chunk.0;
+        let index2 = destination + chunksize - index1;
+
+        let (_, mid) = slice.split_at_mut(index1);
+        let mid = mid.split_at_mut(index2).0;
+
+        mid.rotate_left(chunksize);
+    } else if destination < chunk.0 {
+        let index1 = destination;
+        let index2 = chunk.1 - destination;
+
+        let (_, mid) = slice.split_at_mut(index1);
+        let mid = mid.split_at_mut(index2).0;
+
+        mid.rotate_right(chunksize);
     }
+
+    destination..destination + chunksize
 }
 
 /// A trait declaring the `moveslice` and `try_moveslice` functions.
@@ -104,15 +228,47 @@ pub trait Moveslice<T, R> {
     /// Specifies the errors being returned.
     type Err;
 
-    /// Moves a slice within an array/slice around.
-    /// 
+    /// Moves a slice within an array/slice around, returning the range the
+    /// chunk ended up at.
+    ///
     /// - `bounds` - specifies the range of where the subslice is. Examples: 3..5, 5..=8
     /// - `destination` - specifies where the subslice should be moved to.
-    fn moveslice(&mut self, bounds: R, destination: Self::Target)
+    fn moveslice(&mut self, bounds: R, destination: Self::Target) -> Range<usize>
         where R: RangeBounds<usize>;
 
     /// Similar to `moveslice`, except it does not panic, returning a `Result` instead.
-    fn try_moveslice(&mut self, bounds: R, destination: Self::Target) -> Result<(), Self::Err>
+    fn try_moveslice(&mut self, bounds: R, destination: Self::Target) -> Result<Range<usize>, Self::Err>
+        where R: RangeBounds<usize>;
+
+    /// Swaps two disjoint subslices `a` and `b` around, leaving everything
+    /// outside and in between them untouched.
+    ///
+    /// If `A M B` is the slice split into the range `a`, the gap `M` between
+    /// the two ranges (possibly empty), and the range `b`, then this turns
+    /// it into `B M A`. If `a` or `b` is itself empty, nothing is moved.
+    ///
+    /// - `a` - the first range to swap.
+    /// - `b` - the second range to swap. May lie before or after `a`.
+    fn swapslice(&mut self, a: R, b: R)
+        where R: RangeBounds<usize>;
+
+    /// Similar to `swapslice`, except it does not panic, returning a `Result` instead.
+    fn try_swapslice(&mut self, a: R, b: R) -> Result<(), Self::Err>
+        where R: RangeBounds<usize>;
+
+    /// Similar to `moveslice`, except an out-of-bounds `destination` is
+    /// clamped to the furthest in-bounds position in the requested
+    /// direction instead of erroring.
+    ///
+    /// - `bounds` - specifies the range of where the subslice is. Examples: 3..5, 5..=8
+    /// - `destination` - specifies where the subslice should be moved to. If
+    ///   the chunk would not fit there, it is moved as close to
+    ///   `destination` as it can get instead.
+    fn moveslice_clamped(&mut self, bounds: R, destination: Self::Target) -> Range<usize>
+        where R: RangeBounds<usize>;
+
+    /// Similar to `moveslice_clamped`, except it does not panic, returning a `Result` instead.
+    fn try_moveslice_clamped(&mut self, bounds: R, destination: Self::Target) -> Result<Range<usize>, Self::Err>
         where R: RangeBounds<usize>;
 }
 
@@ -121,65 +277,127 @@ impl<T: 'static,R,A> Moveslice<T,R> for A where A: AsMut<[T]> {
     type Target = usize;
     type Err = Error;
 
-    fn moveslice(&mut self, bounds: R, destination: Self::Target)
-    where R: RangeBounds<usize> 
+    fn moveslice(&mut self, bounds: R, destination: Self::Target) -> Range<usize>
+    where R: RangeBounds<usize>
     {
-        let res = self.try_moveslice(bounds, destination);
-        if let Err(Error::OutOfBoundsMove{len, dest: (x,y)}) = res {
-            panic!("Movement goes beyond bounds. [len = {}, destination = {}..{}]", len, x, y);
-        }
-        else if let Err(Error::InvalidBounds{len, bounds: (x,y)}) = res {
-            panic!("Bounds passed go beyond slice length. [len = {}, bounds = {}..{}]", len, x, y);
+        match self.try_moveslice(bounds, destination) {
+            Ok(range) => range,
+            Err(Error::OutOfBoundsMove{len, dest: (x,y)}) => {
+                panic!("Movement goes beyond bounds. [len = {}, destination = {}..{}]", len, x, y);
+            }
+            Err(Error::InvalidBounds{len, bounds: (x,y)}) => {
+                panic!("Bounds passed go beyond slice length. [len = {}, bounds = {}..{}]", len, x, y);
+            }
+            Err(Error::StartGreaterThanEnd{start, end}) => {
+                panic!("Start of the range is greater than its end. [start = {}, end = {}]", start, end);
+            }
+            Err(other) => panic!("Unexpected error from try_moveslice: {:?}", other),
         }
     }
 
-    fn try_moveslice(&mut self, bounds: R, destination: Self::Target) -> Result<(), Self::Err>
-    where R: RangeBounds<usize> 
+    fn try_moveslice(&mut self, bounds: R, destination: Self::Target) -> Result<Range<usize>, Self::Err>
+    where R: RangeBounds<usize>
     {
         let slice = self.as_mut();
-        let startbound = bounds.start_bound();
-        let endbound = bounds.end_bound();
-        let x = if let Included(x) = startbound {*x} else {0};
-        let y = if let Excluded(x) = endbound {*x}
-                else if let Included(x) = endbound {x+1} 
-                else {slice.len()};
-        let chunk = (x,y);
-
-        if chunk.0 > slice.len() || chunk.1 > slice.len() {
-            return Err(Error::InvalidBounds {
-                len: slice.len(),
-                bounds: chunk
+        let len = slice.len();
+        let chunk = normalize_bounds(&bounds, len)?;
+        let chunksize = chunk.1 - chunk.0;
+
+        if destination > chunk.0 && destination > len - chunksize {
+            return Err(Error::OutOfBoundsMove {
+                len,
+                dest: (destination, destination.saturating_add(chunksize)),
             });
         }
 
-        if destination > chunk.0 {
-            let chunksize = chunk.1 - chunk.0;
-            let index1 = chunk.0;
-            let index2 = destination + chunksize - index1;
+        Ok(move_chunk(slice, chunk, destination))
+    }
 
-            let (_, mid) = slice.split_at_mut(index1);
+    fn swapslice(&mut self, a: R, b: R)
+    where R: RangeBounds<usize>
+    {
+        match self.try_swapslice(a, b) {
+            Ok(()) => (),
+            Err(Error::InvalidBounds{len, bounds: (x,y)}) => {
+                panic!("Bounds passed go beyond slice length. [len = {}, bounds = {}..{}]", len, x, y);
+            }
+            Err(Error::StartGreaterThanEnd{start, end}) => {
+                panic!("Start of the range is greater than its end. [start = {}, end = {}]", start, end);
+            }
+            Err(Error::OverlappingRanges{a: (ax,ay), b: (bx,by)}) => {
+                panic!("Ranges to swap overlap. [a = {}..{}, b = {}..{}]", ax, ay, bx, by);
+            }
+            Err(other) => panic!("Unexpected error from try_swapslice: {:?}", other),
+        }
+    }
 
-            let mid = if index2 <= mid.len() {
-                mid.split_at_mut(index2).0
-            } else {
-                return Err(Error::OutOfBoundsMove {
-                    len: slice.len(),
-                    dest: (destination, destination + chunksize),
-                });
-            };
+    fn try_swapslice(&mut self, a: R, b: R) -> Result<(), Self::Err>
+    where R: RangeBounds<usize>
+    {
+        let slice = self.as_mut();
+        let len = slice.len();
 
-            mid.rotate_left(chunk.1-chunk.0);
-        } else if destination < chunk.0 {
-            let index1 = destination;
-            let index2 = chunk.1 - destination;
+        let a = normalize_bounds(&a, len)?;
+        let b = normalize_bounds(&b, len)?;
 
-            let (_, mid) = slice.split_at_mut(index1);
+        // An empty range carries nothing to swap, so swapping it with
+        // anything (including a range it's adjacent to, or nested inside
+        // of) is a no-op rather than an overlap.
+        if a.0 == a.1 || b.0 == b.1 {
+            return Ok(());
+        }
 
-            let mid = mid.split_at_mut(index2).0;
+        let (lo, hi) = if a.0 <= b.0 {(a, b)} else {(b, a)};
 
-            mid.rotate_right(chunk.1-chunk.0);
+        if lo.1 > hi.0 {
+            return Err(Error::OverlappingRanges {a: lo, b: hi});
         }
 
+        let region = &mut slice[lo.0..hi.1];
+        region.reverse();
+
+        let lo_len = lo.1 - lo.0;
+        let mid_len = hi.0 - lo.1;
+
+        let (hi_part, rest) = region.split_at_mut(hi.1 - hi.0);
+        hi_part.reverse();
+        let (mid_part, lo_part) = rest.split_at_mut(mid_len);
+        mid_part.reverse();
+        lo_part.reverse();
+        debug_assert_eq!(lo_part.len(), lo_len);
+
         Ok(())
     }
+
+    fn moveslice_clamped(&mut self, bounds: R, destination: Self::Target) -> Range<usize>
+    where R: RangeBounds<usize>
+    {
+        match self.try_moveslice_clamped(bounds, destination) {
+            Ok(range) => range,
+            Err(Error::InvalidBounds{len, bounds: (x,y)}) => {
+                panic!("Bounds passed go beyond slice length. [len = {}, bounds = {}..{}]", len, x, y);
+            }
+            Err(Error::StartGreaterThanEnd{start, end}) => {
+                panic!("Start of the range is greater than its end. [start = {}, end = {}]", start, end);
+            }
+            Err(other) => panic!("Unexpected error from try_moveslice_clamped: {:?}", other),
+        }
+    }
+
+    fn try_moveslice_clamped(&mut self, bounds: R, destination: Self::Target) -> Result<Range<usize>, Self::Err>
+    where R: RangeBounds<usize>
+    {
+        let slice = self.as_mut();
+        let len = slice.len();
+        let chunk = normalize_bounds(&bounds, len)?;
+        let chunksize = chunk.1 - chunk.0;
+
+        let destination = if destination > chunk.0 && destination > len - chunksize {
+            len - chunksize
+        } else {
+            destination
+        };
+
+        Ok(move_chunk(slice, chunk, destination))
+    }
 }